@@ -0,0 +1,361 @@
+//! Runtime suite agility for callers (OHTTP, ECH, etc.) that receive a KEM/KDF/AEAD triple as
+//! wire-format algorithm IDs and need to dispatch to the right monomorphized suite without
+//! writing their own match ladder over every `impl_dhkem!` instantiation
+
+extern crate alloc;
+
+use crate::{
+    kdf::{labeled_expand, labeled_extract, Kdf as KdfTrait},
+    kem::Kem as KemTrait,
+    Deserializable, HpkeError, Serializable,
+};
+
+/// A `(KEM_ID, KDF_ID, AEAD_ID)` triple that this crate knows how to dispatch, along with the
+/// human-readable name used in error messages and logging
+struct SuiteEntry {
+    kem_id: u16,
+    kdf_id: u16,
+    aead_id: u16,
+    name: &'static str,
+}
+
+/// Builds the registry of supported suites. A triple this returns doesn't contain is rejected by
+/// [`Config::supported`] before any key material is touched
+fn supported_suites() -> alloc::vec::Vec<SuiteEntry> {
+    let mut suites = alloc::vec::Vec::new();
+
+    #[cfg(feature = "x25519-dalek")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0020,
+        kdf_id: 0x0001,
+        aead_id: 0x0001,
+        name: "DHKEM(X25519, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM",
+    });
+    #[cfg(feature = "p256")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0010,
+        kdf_id: 0x0001,
+        aead_id: 0x0001,
+        name: "DHKEM(P-256, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM",
+    });
+    #[cfg(feature = "p384")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0011,
+        kdf_id: 0x0002,
+        aead_id: 0x0002,
+        name: "DHKEM(P-384, HKDF-SHA384)/HKDF-SHA384/AES-256-GCM",
+    });
+    #[cfg(feature = "p521")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0012,
+        kdf_id: 0x0003,
+        aead_id: 0x0002,
+        name: "DHKEM(P-521, HKDF-SHA512)/HKDF-SHA512/AES-256-GCM",
+    });
+    #[cfg(feature = "secp256k1")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0013,
+        kdf_id: 0x0001,
+        aead_id: 0x0001,
+        name: "DHKEM(secp256k1, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM",
+    });
+    #[cfg(feature = "kyber")]
+    suites.push(SuiteEntry {
+        kem_id: 0x0030,
+        kdf_id: 0x0001,
+        aead_id: 0x0003,
+        name: "X25519Kyber768Draft00/HKDF-SHA256/ChaCha20Poly1305",
+    });
+
+    suites
+}
+
+/// The `(Nk, Nn)` key and nonce sizes, in bytes, for the given AEAD id, per RFC 9180 §7.3. `None`
+/// if the AEAD id isn't one this crate's suite registry ever pairs a KEM/KDF with
+fn aead_key_nonce_sizes(aead_id: u16) -> Option<(usize, usize)> {
+    match aead_id {
+        0x0001 => Some((16, 12)), // AES-128-GCM
+        0x0002 => Some((32, 12)), // AES-256-GCM
+        0x0003 => Some((32, 12)), // ChaCha20Poly1305
+        _ => None,
+    }
+}
+
+/// The `(key, base_nonce, exporter_secret)` produced by [`key_schedule`]
+type KeyScheduleOutput = (
+    alloc::boxed::Box<[u8]>,
+    alloc::boxed::Box<[u8]>,
+    alloc::boxed::Box<[u8]>,
+);
+
+/// Runs RFC 9180 §5.1's `KeySchedule` in `mode_base` (no PSK, no sender authentication) over a
+/// KEM shared secret, producing the AEAD key, base nonce, and exporter secret that a real
+/// `Context` would seal/open/export with
+fn key_schedule<K: KdfTrait>(
+    shared_secret: &[u8],
+    kem_id: u16,
+    kdf_id: u16,
+    aead_id: u16,
+    info: &[u8],
+) -> Result<KeyScheduleOutput, HpkeError> {
+    let (nk, nn) = aead_key_nonce_sizes(aead_id).ok_or(HpkeError::EncapError)?;
+
+    // suite_id = "HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2)
+    let mut suite_id = [0u8; 10];
+    suite_id[..4].copy_from_slice(b"HPKE");
+    suite_id[4..6].copy_from_slice(&kem_id.to_be_bytes());
+    suite_id[6..8].copy_from_slice(&kdf_id.to_be_bytes());
+    suite_id[8..].copy_from_slice(&aead_id.to_be_bytes());
+
+    // mode_base = 0x00; no PSK is used in this mode, so psk_id is empty
+    const MODE_BASE: u8 = 0x00;
+    let (psk_id_hash, _) = labeled_extract::<K>(None, &suite_id, b"psk_id_hash", &[]);
+    let (info_hash, _) = labeled_extract::<K>(None, &suite_id, b"info_hash", info);
+
+    let mut key_schedule_context =
+        alloc::vec::Vec::with_capacity(1 + psk_id_hash.len() + info_hash.len());
+    key_schedule_context.push(MODE_BASE);
+    key_schedule_context.extend_from_slice(&psk_id_hash);
+    key_schedule_context.extend_from_slice(&info_hash);
+
+    // secret = LabeledExtract(shared_secret, "secret", psk); psk is empty in mode_base
+    let (_, secret_hkdf) = labeled_extract::<K>(Some(shared_secret), &suite_id, b"secret", &[]);
+
+    // The HKDF-Expand calls below only error if the output is over 255x the KDF's hash size.
+    // `nk`/`nn`/`info_hash.len()` are all tiny (at most 64 bytes) for every suite in our registry,
+    // so we don't worry about it, matching the DH-KEMs' `extract_and_expand` convention
+    let mut key = alloc::vec![0u8; nk];
+    labeled_expand::<K>(&secret_hkdf, &suite_id, b"key", &key_schedule_context, &mut key)
+        .expect("AEAD key is way too big");
+
+    let mut base_nonce = alloc::vec![0u8; nn];
+    labeled_expand::<K>(
+        &secret_hkdf,
+        &suite_id,
+        b"base_nonce",
+        &key_schedule_context,
+        &mut base_nonce,
+    )
+    .expect("AEAD nonce is way too big");
+
+    let mut exporter_secret = alloc::vec![0u8; info_hash.len()];
+    labeled_expand::<K>(
+        &secret_hkdf,
+        &suite_id,
+        b"exp",
+        &key_schedule_context,
+        &mut exporter_secret,
+    )
+    .expect("exporter secret is way too big");
+
+    Ok((
+        key.into_boxed_slice(),
+        base_nonce.into_boxed_slice(),
+        exporter_secret.into_boxed_slice(),
+    ))
+}
+
+/// The result of a dynamic `setup_sender`/`setup_receiver` call: the AEAD key, base nonce, and
+/// exporter secret derived from the KEM shared secret via RFC 9180 §5.1's `KeySchedule`, along
+/// with the suite they were derived under. This crate doesn't implement AEAD sealing/opening
+/// itself, so callers drive the actual encryption with whichever AEAD crate matches `aead_id`
+pub struct DynamicContext {
+    kem_id: u16,
+    kdf_id: u16,
+    aead_id: u16,
+    key: alloc::boxed::Box<[u8]>,
+    base_nonce: alloc::boxed::Box<[u8]>,
+    exporter_secret: alloc::boxed::Box<[u8]>,
+}
+
+impl DynamicContext {
+    /// The KEM id this context was established under
+    pub fn kem_id(&self) -> u16 {
+        self.kem_id
+    }
+
+    /// The KDF id this context was established under
+    pub fn kdf_id(&self) -> u16 {
+        self.kdf_id
+    }
+
+    /// The AEAD id this context was established under
+    pub fn aead_id(&self) -> u16 {
+        self.aead_id
+    }
+
+    /// The AEAD key derived for this context, sized per `aead_id`'s `Nk`
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The AEAD base nonce derived for this context, sized per `aead_id`'s `Nn`
+    pub fn base_nonce(&self) -> &[u8] {
+        &self.base_nonce
+    }
+
+    /// The exporter secret derived for this context, for use with RFC 9180 §5.3's `Export`
+    pub fn exporter_secret(&self) -> &[u8] {
+        &self.exporter_secret
+    }
+
+    /// Overwrites this context's derived secrets with zeroes. This is a best-effort wipe with no
+    /// guarantee against compiler reordering or elision; enable the `zeroize` feature for a
+    /// guaranteed wipe on drop
+    pub fn non_secure_erase(&mut self) {
+        self.key.iter_mut().for_each(|b| *b = 0);
+        self.base_nonce.iter_mut().for_each(|b| *b = 0);
+        self.exporter_secret.iter_mut().for_each(|b| *b = 0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for DynamicContext {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.key.zeroize();
+        self.base_nonce.zeroize();
+        self.exporter_secret.zeroize();
+    }
+}
+
+/// Identifies a suite by its wire-format algorithm IDs and dispatches to the matching
+/// monomorphized KEM/KDF/AEAD instantiation at runtime, so callers that only know algorithm IDs
+/// (e.g. parsed off the wire, as in OHTTP/ECH) don't have to write their own match ladder
+pub struct Config {
+    pub kem_id: u16,
+    pub kdf_id: u16,
+    pub aead_id: u16,
+}
+
+impl Config {
+    /// Returns `true` iff this crate has an implementation wired up for `(kem_id, kdf_id,
+    /// aead_id)`. Callers should check this before doing anything else with untrusted wire-format
+    /// IDs, so that unknown suites are rejected before any key material is touched
+    pub fn supported(&self) -> bool {
+        supported_suites().iter().any(|s| {
+            s.kem_id == self.kem_id && s.kdf_id == self.kdf_id && s.aead_id == self.aead_id
+        })
+    }
+
+    /// The human-readable name of this suite, if supported
+    pub fn name(&self) -> Option<&'static str> {
+        supported_suites()
+            .iter()
+            .find(|s| {
+                s.kem_id == self.kem_id && s.kdf_id == self.kdf_id && s.aead_id == self.aead_id
+            })
+            .map(|s| s.name)
+    }
+
+    /// Runs `Encap` against `pk_recip_bytes` (a serialized recipient public key for this suite's
+    /// KEM), then `KeySchedule` over the resulting shared secret and `info`, and returns the
+    /// serialized encapped key alongside the resulting [`DynamicContext`]
+    pub fn setup_sender(
+        &self,
+        pk_recip_bytes: &[u8],
+        info: &[u8],
+        csprng: &mut (impl rand_core::CryptoRng + rand_core::RngCore),
+    ) -> Result<(alloc::vec::Vec<u8>, DynamicContext), HpkeError> {
+        if !self.supported() {
+            return Err(HpkeError::InvalidEncoding);
+        }
+
+        macro_rules! dispatch {
+            ($kem:ty, $kdf:ty) => {{
+                let pk_recip = <$kem as KemTrait>::PublicKey::from_bytes(pk_recip_bytes)?;
+                let (sk_eph, _) = <$kem as KemTrait>::gen_keypair(csprng);
+                let (shared_secret, encapped_key) =
+                    <$kem as KemTrait>::encap_with_eph(&pk_recip, None, sk_eph, csprng)?;
+                let (key, base_nonce, exporter_secret) = key_schedule::<$kdf>(
+                    &shared_secret.0,
+                    self.kem_id,
+                    self.kdf_id,
+                    self.aead_id,
+                    info,
+                )?;
+                return Ok((
+                    encapped_key.to_bytes().to_vec(),
+                    DynamicContext {
+                        kem_id: self.kem_id,
+                        kdf_id: self.kdf_id,
+                        aead_id: self.aead_id,
+                        key,
+                        base_nonce,
+                        exporter_secret,
+                    },
+                ));
+            }};
+        }
+
+        match self.kem_id {
+            #[cfg(feature = "x25519-dalek")]
+            0x0020 => dispatch!(crate::kem::X25519HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "p256")]
+            0x0010 => dispatch!(crate::kem::DhP256HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "p384")]
+            0x0011 => dispatch!(crate::kem::DhP384HkdfSha384, crate::kdf::HkdfSha384),
+            #[cfg(feature = "p521")]
+            0x0012 => dispatch!(crate::kem::DhP521HkdfSha512, crate::kdf::HkdfSha512),
+            #[cfg(feature = "secp256k1")]
+            0x0013 => dispatch!(crate::kem::DhSecp256k1HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "kyber")]
+            0x0030 => dispatch!(crate::kem::X25519Kyber768Draft00, crate::kdf::HkdfSha256),
+            _ => Err(HpkeError::EncapError),
+        }
+    }
+
+    /// Runs `Decap` against `sk_recip_bytes` (a serialized recipient private key for this suite's
+    /// KEM) and the serialized `encapped_key_bytes`, then `KeySchedule` over the resulting shared
+    /// secret and `info`, returning the resulting [`DynamicContext`]
+    pub fn setup_receiver(
+        &self,
+        sk_recip_bytes: &[u8],
+        encapped_key_bytes: &[u8],
+        info: &[u8],
+    ) -> Result<DynamicContext, HpkeError> {
+        if !self.supported() {
+            return Err(HpkeError::InvalidEncoding);
+        }
+
+        macro_rules! dispatch {
+            ($kem:ty, $kdf:ty) => {{
+                let sk_recip = <$kem as KemTrait>::PrivateKey::from_bytes(sk_recip_bytes)?;
+                let encapped_key =
+                    <$kem as KemTrait>::EncappedKey::from_bytes(encapped_key_bytes)?;
+                let shared_secret = <$kem as KemTrait>::decap(&sk_recip, None, &encapped_key)?;
+                let (key, base_nonce, exporter_secret) = key_schedule::<$kdf>(
+                    &shared_secret.0,
+                    self.kem_id,
+                    self.kdf_id,
+                    self.aead_id,
+                    info,
+                )?;
+                return Ok(DynamicContext {
+                    kem_id: self.kem_id,
+                    kdf_id: self.kdf_id,
+                    aead_id: self.aead_id,
+                    key,
+                    base_nonce,
+                    exporter_secret,
+                });
+            }};
+        }
+
+        match self.kem_id {
+            #[cfg(feature = "x25519-dalek")]
+            0x0020 => dispatch!(crate::kem::X25519HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "p256")]
+            0x0010 => dispatch!(crate::kem::DhP256HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "p384")]
+            0x0011 => dispatch!(crate::kem::DhP384HkdfSha384, crate::kdf::HkdfSha384),
+            #[cfg(feature = "p521")]
+            0x0012 => dispatch!(crate::kem::DhP521HkdfSha512, crate::kdf::HkdfSha512),
+            #[cfg(feature = "secp256k1")]
+            0x0013 => dispatch!(crate::kem::DhSecp256k1HkdfSha256, crate::kdf::HkdfSha256),
+            #[cfg(feature = "kyber")]
+            0x0030 => dispatch!(crate::kem::X25519Kyber768Draft00, crate::kdf::HkdfSha256),
+            _ => Err(HpkeError::DecapError),
+        }
+    }
+}
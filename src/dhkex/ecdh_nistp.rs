@@ -0,0 +1,183 @@
+use crate::{
+    dhkex::{DhError, DhKeyExchange},
+    kdf::Kdf as KdfTrait,
+    Deserializable, HpkeError, Serializable,
+};
+
+use elliptic_curve::{
+    ecdh::diffie_hellman,
+    sec1::{FromEncodedPoint, ToEncodedPoint},
+    Curve, FieldBytesSize, PrimeField,
+};
+use generic_array::{typenum, GenericArray};
+use hkdf::Hkdf;
+use paste::paste;
+
+/// Generates a `DhKeyExchange` impl for the given NIST curve, generalizing the rejection-sampling
+/// `DeriveKeyPair` loop (RFC 9180 §7.1.3) over that curve's order instead of hard-coding P-256
+macro_rules! impl_nistp_dhkex {
+    ($curve_mod:ident, $curve_ty:ty, $dh_name:ident, $bitmask:expr, $doc_str:expr) => {
+        paste! {
+            /// The byte length of a field element (Nfe) for this curve
+            type [<$dh_name Nfe>] =
+                <FieldBytesSize<$curve_ty> as elliptic_curve::bigint::ArrayEncoding<_>>::ByteSize;
+
+            #[doc = $doc_str]
+            pub struct $dh_name {}
+
+            #[doc = concat!("A public key in ", $doc_str)]
+            #[derive(Clone)]
+            pub struct [<$dh_name PublicKey>](pub(crate) $curve_mod::PublicKey);
+
+            #[doc = concat!("A private key in ", $doc_str)]
+            #[derive(Clone)]
+            pub struct [<$dh_name PrivateKey>](pub(crate) $curve_mod::SecretKey);
+
+            #[doc = concat!(
+                "The output of [`DhKeyExchange::dh`] for ",
+                $doc_str,
+                ": the raw x-coordinate (Nfe bytes), per RFC 9180 §4.1, rather than a SEC1-encoded point"
+            )]
+            pub struct [<$dh_name DhResult>](pub(crate) GenericArray<u8, [<$dh_name Nfe>]>);
+
+            impl Serializable for [<$dh_name DhResult>] {
+                type OutputSize = [<$dh_name Nfe>];
+
+                fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+                    self.0.clone()
+                }
+            }
+
+            impl [<$dh_name PrivateKey>] {
+                /// Overwrites this key's scalar with the placeholder value 1. The all-zero byte
+                /// string isn't a valid scalar for this curve, so this is the smallest
+                /// fixed, non-secret value we can swap in. This is a best-effort wipe with no
+                /// guarantee against compiler reordering or elision; enable the `zeroize`
+                /// feature for a guaranteed wipe on drop
+                pub fn non_secure_erase(&mut self) {
+                    let mut placeholder = GenericArray::<u8, [<$dh_name Nfe>]>::default();
+                    *placeholder.last_mut().expect("Nfe is nonzero") = 1;
+                    self.0 = $curve_mod::SecretKey::from_bytes(&placeholder)
+                        .expect("scalar value 1 is a valid secret key on every supported curve");
+                }
+            }
+
+            // `$curve_mod::SecretKey` already zeroizes its backing scalar on drop when this
+            // crate's `zeroize` feature enables the matching feature on that curve crate, so no
+            // `Drop` impl is needed here
+
+            impl Serializable for [<$dh_name PublicKey>] {
+                // RFC 9180 §7.1.1 specifies the uncompressed SEC1 encoding (2*Nfe + 1 bytes) for
+                // NIST DHKEM public keys
+                type OutputSize = typenum::Sum<typenum::Sum<[<$dh_name Nfe>], [<$dh_name Nfe>]>, typenum::U1>;
+
+                fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+                    GenericArray::clone_from_slice(self.0.to_encoded_point(false).as_bytes())
+                }
+            }
+
+            impl Deserializable for [<$dh_name PublicKey>] {
+                fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+                    let encoded_point = elliptic_curve::sec1::EncodedPoint::<$curve_ty>::from_bytes(encoded)
+                        .map_err(|_| HpkeError::InvalidEncoding)?;
+                    let opt: Option<$curve_mod::PublicKey> =
+                        $curve_mod::PublicKey::from_encoded_point(&encoded_point).into();
+                    opt.map([<$dh_name PublicKey>]).ok_or(HpkeError::InvalidEncoding)
+                }
+            }
+
+            impl Serializable for [<$dh_name PrivateKey>] {
+                type OutputSize = [<$dh_name Nfe>];
+
+                fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+                    self.0.to_bytes()
+                }
+            }
+
+            impl Deserializable for [<$dh_name PrivateKey>] {
+                fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+                    $curve_mod::SecretKey::from_slice(encoded)
+                        .map([<$dh_name PrivateKey>])
+                        .map_err(|_| HpkeError::InvalidEncoding)
+                }
+            }
+
+            impl DhKeyExchange for $dh_name {
+                type PublicKey = [<$dh_name PublicKey>];
+                type PrivateKey = [<$dh_name PrivateKey>];
+                type KexResult = [<$dh_name DhResult>];
+
+                fn dh(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<Self::KexResult, DhError> {
+                    let shared = diffie_hellman(sk.0.to_nonzero_scalar(), pk.0.as_affine());
+                    // The DH output used by DHKEM is the raw x-coordinate (RFC 9180 §4.1), not a
+                    // SEC1-encoded point, so carry it in its own type instead of re-parsing it as
+                    // a pubkey
+                    Ok([<$dh_name DhResult>](GenericArray::clone_from_slice(
+                        shared.raw_secret_bytes().as_slice(),
+                    )))
+                }
+
+                fn sk_to_pk(sk: &Self::PrivateKey) -> Self::PublicKey {
+                    [<$dh_name PublicKey>](sk.0.public_key())
+                }
+
+                fn derive_keypair<K: KdfTrait>(
+                    suite_id: &[u8],
+                    ikm: &[u8],
+                ) -> (Self::PrivateKey, Self::PublicKey) {
+                    // dkp_prk = LabeledExtract("", "dkp_prk", ikm)
+                    let mut labeled_ikm = Vec::with_capacity(7 + suite_id.len() + 7 + ikm.len());
+                    labeled_ikm.extend_from_slice(b"HPKE-v1");
+                    labeled_ikm.extend_from_slice(suite_id);
+                    labeled_ikm.extend_from_slice(b"dkp_prk");
+                    labeled_ikm.extend_from_slice(ikm);
+                    let (_, hkdf) = Hkdf::<K::HashImpl>::extract(None, &labeled_ikm);
+
+                    // Rejection-sample candidate scalars against this curve's order, per RFC 9180
+                    // §7.1.3's generic `DeriveKeyPair` for NIST curves
+                    let mut counter: u16 = 0;
+                    loop {
+                        if counter >= 256 {
+                            panic!("DeriveKeyPair failed to find a valid scalar in 256 attempts");
+                        }
+
+                        let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + 9 + 1);
+                        labeled_info.extend_from_slice(
+                            &<[<$dh_name Nfe>] as typenum::Unsigned>::U16.to_be_bytes(),
+                        );
+                        labeled_info.extend_from_slice(b"HPKE-v1");
+                        labeled_info.extend_from_slice(suite_id);
+                        labeled_info.extend_from_slice(b"candidate");
+                        labeled_info.push(counter as u8);
+
+                        let mut bytes = GenericArray::<u8, [<$dh_name Nfe>]>::default();
+                        hkdf.expand(&labeled_info, &mut bytes)
+                            .expect("output size is a valid HKDF-Expand length");
+
+                        // RFC 9180 §7.1.3: mask off the bits of the candidate that don't belong
+                        // to the curve's order before checking it. This is a no-op (0xff) for
+                        // curves whose Nsk is an exact multiple of 8 bits, but P-521's 66-byte
+                        // candidate has 7 unused high bits that must be cleared
+                        bytes[0] &= $bitmask;
+
+                        if let Ok(sk) = $curve_mod::SecretKey::from_bytes(&bytes) {
+                            let pk = sk.public_key();
+                            return ([<$dh_name PrivateKey>](sk), [<$dh_name PublicKey>](pk));
+                        }
+
+                        counter += 1;
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "p256")]
+impl_nistp_dhkex!(p256, p256::NistP256, DhP256, 0xff, "DH over NIST curve P-256");
+
+#[cfg(feature = "p384")]
+impl_nistp_dhkex!(p384, p384::NistP384, DhP384, 0xff, "DH over NIST curve P-384");
+
+#[cfg(feature = "p521")]
+impl_nistp_dhkex!(p521, p521::NistP521, DhP521, 0x01, "DH over NIST curve P-521");
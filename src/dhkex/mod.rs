@@ -0,0 +1,47 @@
+use crate::{kdf::Kdf as KdfTrait, Deserializable, HpkeError, Serializable};
+
+#[cfg(feature = "x25519-dalek")]
+pub mod x25519;
+
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521"))]
+pub mod ecdh_nistp;
+
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+
+/// The maximum size, in bytes, of a serialized public key or KEX output among all the DH groups
+/// this crate supports. P-521's uncompressed SEC1 encoding (133 bytes) is the largest. Used to
+/// size [`concat_with_known_maxlen`](crate::concat_with_known_maxlen) buffers, which in
+/// `AuthEncap`/`AuthDecap` hold up to three concatenated public keys/KEX outputs, so this is 3x
+/// that size
+pub(crate) const MAX_PUBKEY_SIZE: usize = 3 * 133;
+
+/// An error signifying that a key exchange failed, e.g. due to an invalid point or scalar being
+/// given to [`DhKeyExchange::dh`]
+#[derive(Debug, Eq, PartialEq)]
+pub struct DhError;
+
+/// Represents a Diffie-Hellman group usable in a DHKEM, per RFC 9180 §4.1 / §7.1.3
+pub trait DhKeyExchange {
+    /// A public key in this group
+    type PublicKey: Clone + Serializable + Deserializable;
+    /// A private (secret) key in this group
+    type PrivateKey: Clone + Serializable + Deserializable;
+    /// The output of [`Self::dh`]. This is serializable so it can be fed into
+    /// `ExtractAndExpand`, but it is not necessarily a valid [`Self::PublicKey`]: for the NIST
+    /// curves it's the bare x-coordinate, not a SEC1-encoded point
+    type KexResult: Serializable;
+
+    /// Computes the DH operation on the given private and public keys
+    fn dh(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<Self::KexResult, DhError>;
+
+    /// Computes the public key corresponding to the given private key
+    fn sk_to_pk(sk: &Self::PrivateKey) -> Self::PublicKey;
+
+    /// Deterministically derives a keypair from the given keying material, per RFC 9180 §7.1.3's
+    /// `DeriveKeyPair`
+    fn derive_keypair<K: KdfTrait>(
+        suite_id: &[u8],
+        ikm: &[u8],
+    ) -> (Self::PrivateKey, Self::PublicKey);
+}
@@ -0,0 +1,146 @@
+use crate::{
+    dhkex::{DhError, DhKeyExchange},
+    kdf::Kdf as KdfTrait,
+    Deserializable, HpkeError, Serializable,
+};
+
+use generic_array::{typenum, GenericArray};
+use hkdf::Hkdf;
+use k256::{
+    elliptic_curve::{ecdh::diffie_hellman, sec1::ToEncodedPoint, PrimeField},
+    PublicKey, Scalar, SecretKey,
+};
+
+/// DH over secp256k1, the curve used by Bitcoin and Nostr
+pub struct Secp256k1 {}
+
+/// A public key on secp256k1, serialized in compressed SEC1 form (33 bytes)
+#[derive(Clone)]
+pub struct PublicKeyWrapper(pub(crate) PublicKey);
+
+/// A private key on secp256k1
+#[derive(Clone)]
+pub struct PrivateKeyWrapper(pub(crate) SecretKey);
+
+impl PrivateKeyWrapper {
+    /// Overwrites this key's scalar with the placeholder value 1. The all-zero byte string isn't
+    /// a valid scalar for this curve, so this is the smallest fixed, non-secret value we can swap
+    /// in. This is a best-effort wipe with no guarantee against compiler reordering or elision;
+    /// enable the `zeroize` feature for a guaranteed wipe on drop
+    pub fn non_secure_erase(&mut self) {
+        let mut placeholder = GenericArray::<u8, typenum::U32>::default();
+        placeholder[31] = 1;
+        self.0 = SecretKey::from_bytes(&placeholder)
+            .expect("scalar value 1 is a valid secret key on secp256k1");
+    }
+}
+
+// `k256::SecretKey` already zeroizes its backing scalar on drop when this crate's `zeroize`
+// feature enables the matching feature on `k256`, so no `Drop` impl is needed here
+
+/// The output of [`DhKeyExchange::dh`]: the raw x-coordinate (32 bytes), per RFC 9180 §4.1,
+/// rather than a SEC1-encoded point
+pub struct DhResult(GenericArray<u8, typenum::U32>);
+
+impl Serializable for DhResult {
+    type OutputSize = typenum::U32;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        self.0.clone()
+    }
+}
+
+impl Serializable for PublicKeyWrapper {
+    type OutputSize = typenum::U33;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        GenericArray::clone_from_slice(self.0.to_encoded_point(true).as_bytes())
+    }
+}
+
+impl Deserializable for PublicKeyWrapper {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        PublicKey::from_sec1_bytes(encoded)
+            .map(PublicKeyWrapper)
+            .map_err(|_| HpkeError::InvalidEncoding)
+    }
+}
+
+impl Serializable for PrivateKeyWrapper {
+    type OutputSize = typenum::U32;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        self.0.to_bytes()
+    }
+}
+
+impl Deserializable for PrivateKeyWrapper {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        SecretKey::from_slice(encoded)
+            .map(PrivateKeyWrapper)
+            .map_err(|_| HpkeError::InvalidEncoding)
+    }
+}
+
+impl DhKeyExchange for Secp256k1 {
+    type PublicKey = PublicKeyWrapper;
+    type PrivateKey = PrivateKeyWrapper;
+    type KexResult = DhResult;
+
+    fn dh(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<Self::KexResult, DhError> {
+        // The DH output used by DHKEM is the raw x-coordinate (RFC 9180 §4.1), not a SEC1-encoded
+        // point, to stay byte-for-byte compatible with the generic DHKEM formula
+        let shared = diffie_hellman(sk.0.to_nonzero_scalar(), pk.0.as_affine());
+        Ok(DhResult(GenericArray::clone_from_slice(
+            shared.raw_secret_bytes().as_slice(),
+        )))
+    }
+
+    fn sk_to_pk(sk: &Self::PrivateKey) -> Self::PublicKey {
+        PublicKeyWrapper(sk.0.public_key())
+    }
+
+    fn derive_keypair<K: KdfTrait>(
+        suite_id: &[u8],
+        ikm: &[u8],
+    ) -> (Self::PrivateKey, Self::PublicKey) {
+        // dkp_prk = LabeledExtract("", "dkp_prk", ikm)
+        let mut labeled_ikm = Vec::with_capacity(7 + suite_id.len() + 7 + ikm.len());
+        labeled_ikm.extend_from_slice(b"HPKE-v1");
+        labeled_ikm.extend_from_slice(suite_id);
+        labeled_ikm.extend_from_slice(b"dkp_prk");
+        labeled_ikm.extend_from_slice(ikm);
+        let (_, hkdf) = Hkdf::<K::HashImpl>::extract(None, &labeled_ikm);
+
+        // Same rejection-sampling loop as the NIST curves in `ecdh_nistp`, but rejecting against
+        // the secp256k1 curve order instead of a NIST prime order
+        let mut counter: u16 = 0;
+        loop {
+            if counter >= 256 {
+                panic!("DeriveKeyPair failed to find a valid scalar in 256 attempts");
+            }
+
+            let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + 9 + 1);
+            labeled_info.extend_from_slice(&32u16.to_be_bytes());
+            labeled_info.extend_from_slice(b"HPKE-v1");
+            labeled_info.extend_from_slice(suite_id);
+            labeled_info.extend_from_slice(b"candidate");
+            labeled_info.push(counter as u8);
+
+            let mut bytes = GenericArray::<u8, typenum::U32>::default();
+            hkdf.expand(&labeled_info, &mut bytes)
+                .expect("32 is a valid HKDF-Expand output length");
+
+            let scalar_opt: Option<Scalar> = Scalar::from_repr(bytes).into();
+            if let Some(scalar) = scalar_opt {
+                if !bool::from(scalar.is_zero()) {
+                    let sk = SecretKey::from(scalar);
+                    let pk = sk.public_key();
+                    return (PrivateKeyWrapper(sk), PublicKeyWrapper(pk));
+                }
+            }
+
+            counter += 1;
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    dhkex::{DhError, DhKeyExchange},
+    kdf::Kdf as KdfTrait,
+    Deserializable, HpkeError, Serializable,
+};
+
+use generic_array::{typenum, GenericArray};
+use hkdf::Hkdf;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Represents DH over Curve25519
+pub struct X25519 {}
+
+/// A public key in Curve25519
+#[derive(Clone)]
+pub struct PublicKeyWrapper(pub(crate) PublicKey);
+
+/// A private key in Curve25519
+#[derive(Clone)]
+pub struct PrivateKeyWrapper(pub(crate) StaticSecret);
+
+impl PrivateKeyWrapper {
+    /// Overwrites this key's bytes with zeroes. This is a best-effort wipe with no guarantee
+    /// against compiler reordering or elision; enable the `zeroize` feature for a guaranteed wipe
+    /// on drop
+    pub fn non_secure_erase(&mut self) {
+        self.0 = StaticSecret::from([0u8; 32]);
+    }
+}
+
+// `x25519_dalek::StaticSecret` already zeroizes its bytes on drop when this crate's `zeroize`
+// feature enables the matching feature on `x25519-dalek`, so no `Drop` impl is needed here
+
+impl Serializable for PublicKeyWrapper {
+    type OutputSize = typenum::U32;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        GenericArray::clone_from_slice(self.0.as_bytes())
+    }
+}
+
+impl Deserializable for PublicKeyWrapper {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        if encoded.len() != 32 {
+            return Err(HpkeError::InvalidEncoding);
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(encoded);
+        Ok(PublicKeyWrapper(PublicKey::from(buf)))
+    }
+}
+
+impl Serializable for PrivateKeyWrapper {
+    type OutputSize = typenum::U32;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        GenericArray::clone_from_slice(&self.0.to_bytes())
+    }
+}
+
+impl Deserializable for PrivateKeyWrapper {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        if encoded.len() != 32 {
+            return Err(HpkeError::InvalidEncoding);
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(encoded);
+        Ok(PrivateKeyWrapper(StaticSecret::from(buf)))
+    }
+}
+
+impl DhKeyExchange for X25519 {
+    type PublicKey = PublicKeyWrapper;
+    type PrivateKey = PrivateKeyWrapper;
+    type KexResult = PublicKeyWrapper;
+
+    fn dh(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<Self::KexResult, DhError> {
+        let shared = sk.0.diffie_hellman(&pk.0);
+        Ok(PublicKeyWrapper(PublicKey::from(*shared.as_bytes())))
+    }
+
+    fn sk_to_pk(sk: &Self::PrivateKey) -> Self::PublicKey {
+        PublicKeyWrapper(PublicKey::from(&sk.0))
+    }
+
+    fn derive_keypair<K: KdfTrait>(
+        suite_id: &[u8],
+        ikm: &[u8],
+    ) -> (Self::PrivateKey, Self::PublicKey) {
+        // dkp_prk = LabeledExtract("", "dkp_prk", ikm); sk = LabeledExpand(dkp_prk, "sk", "", 32)
+        let mut labeled_ikm = Vec::with_capacity(7 + suite_id.len() + 7 + ikm.len());
+        labeled_ikm.extend_from_slice(b"HPKE-v1");
+        labeled_ikm.extend_from_slice(suite_id);
+        labeled_ikm.extend_from_slice(b"dkp_prk");
+        labeled_ikm.extend_from_slice(ikm);
+        let (_, hkdf) = Hkdf::<K::HashImpl>::extract(None, &labeled_ikm);
+
+        let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + 2);
+        labeled_info.extend_from_slice(&32u16.to_be_bytes());
+        labeled_info.extend_from_slice(b"HPKE-v1");
+        labeled_info.extend_from_slice(suite_id);
+        labeled_info.extend_from_slice(b"sk");
+
+        let mut sk_bytes = [0u8; 32];
+        hkdf.expand(&labeled_info, &mut sk_bytes)
+            .expect("32 is a valid HKDF-Expand output length");
+
+        let sk = StaticSecret::from(sk_bytes);
+        let pk = PublicKey::from(&sk);
+        (PrivateKeyWrapper(sk), PublicKeyWrapper(pk))
+    }
+}
@@ -0,0 +1,101 @@
+use digest::{core_api::CoreProxy, FixedOutput};
+use hkdf::{Hkdf, HmacImpl};
+use hmac::Hmac;
+
+/// Represents key derivation functionality for an HPKE ciphersuite
+///
+/// The `where` clause binds `Hmac<HashImpl>: HmacImpl<HashImpl>`, the bound `hkdf` actually needs
+/// to build its default `Hkdf<HashImpl, Hmac<HashImpl>>` instantiation. `CoreProxy` alone isn't
+/// enough: `hkdf` additionally requires `HashImpl`'s `core_api` internals (`HashMarker`,
+/// `UpdateCore`, `FixedOutputCore`, an `Eager` buffer, and a sub-256-byte block size) to line up,
+/// which only concrete hash impls like `sha2::Sha256` can attest to, not a hand-written bound list
+pub trait Kdf
+where
+    Hmac<Self::HashImpl>: HmacImpl<Self::HashImpl>,
+{
+    /// The hash function this KDF is built on top of
+    #[doc(hidden)]
+    type HashImpl: FixedOutput + Clone + digest::Digest + CoreProxy;
+
+    /// The algorithm identifier for this KDF, as given in RFC 9180 §7.2
+    const KDF_ID: u16;
+}
+
+/// The implementation of HKDF-SHA256
+pub struct HkdfSha256;
+
+impl Kdf for HkdfSha256 {
+    #[doc(hidden)]
+    type HashImpl = sha2::Sha256;
+
+    const KDF_ID: u16 = 0x0001;
+}
+
+/// The implementation of HKDF-SHA384
+pub struct HkdfSha384;
+
+impl Kdf for HkdfSha384 {
+    #[doc(hidden)]
+    type HashImpl = sha2::Sha384;
+
+    const KDF_ID: u16 = 0x0002;
+}
+
+/// The implementation of HKDF-SHA512
+pub struct HkdfSha512;
+
+impl Kdf for HkdfSha512 {
+    #[doc(hidden)]
+    type HashImpl = sha2::Sha512;
+
+    const KDF_ID: u16 = 0x0003;
+}
+
+/// Computes RFC 9180 §4's `LabeledExtract(salt, label, ikm)`, returning the resulting PRK bytes
+/// alongside the `Hkdf` so the caller can run one or more `LabeledExpand`s against it
+pub(crate) fn labeled_extract<K: Kdf>(
+    salt: Option<&[u8]>,
+    suite_id: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> (Vec<u8>, Hkdf<K::HashImpl>) {
+    let mut labeled_ikm = Vec::with_capacity(7 + suite_id.len() + label.len() + ikm.len());
+    labeled_ikm.extend_from_slice(b"HPKE-v1");
+    labeled_ikm.extend_from_slice(suite_id);
+    labeled_ikm.extend_from_slice(label);
+    labeled_ikm.extend_from_slice(ikm);
+    let (prk, hkdf) = Hkdf::<K::HashImpl>::extract(salt, &labeled_ikm);
+    (prk.to_vec(), hkdf)
+}
+
+/// Computes RFC 9180 §4's `LabeledExpand(prk, label, info, L)` into `out`, using the `Hkdf`
+/// produced by a prior [`labeled_extract`] call
+pub(crate) fn labeled_expand<K: Kdf>(
+    hkdf: &Hkdf<K::HashImpl>,
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[u8],
+    out: &mut [u8],
+) -> Result<(), hkdf::InvalidLength> {
+    // labeled_info = I2OSP(L, 2) || "HPKE-v1" || suite_id || label || info
+    let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + label.len() + info.len());
+    labeled_info.extend_from_slice(&(out.len() as u16).to_be_bytes());
+    labeled_info.extend_from_slice(b"HPKE-v1");
+    labeled_info.extend_from_slice(suite_id);
+    labeled_info.extend_from_slice(label);
+    labeled_info.extend_from_slice(info);
+    hkdf.expand(&labeled_info, out)
+}
+
+/// Computes `LabeledExtract` followed by `LabeledExpand` of `ikm` into `out`, binding the
+/// operation to `suite_id` and `info` exactly as specified in RFC 9180 §4. This is used by the
+/// DH-KEMs to turn a raw DH output into a KEM shared secret
+pub(crate) fn extract_and_expand<K: Kdf>(
+    ikm: &[u8],
+    suite_id: &[u8],
+    info: &[u8],
+    out: &mut [u8],
+) -> Result<(), hkdf::InvalidLength> {
+    let (_, hkdf) = labeled_extract::<K>(None, suite_id, b"eae_prk", ikm);
+    labeled_expand::<K>(&hkdf, suite_id, b"shared_secret", info, out)
+}
@@ -152,10 +152,11 @@ macro_rules! impl_dhkem {
             /// Returns a shared secret and encapped key on success. If an error happened during
             /// key exchange, returns `Err(HpkeError::EncapError)`.
             #[doc(hidden)]
-            fn encap_with_eph(
+            fn encap_with_eph<R: CryptoRng + RngCore>(
                 pk_recip: &Self::PublicKey,
                 sender_id_keypair: Option<&(Self::PrivateKey, Self::PublicKey)>,
                 sk_eph: Self::PrivateKey,
+                _csprng: &mut R,
             ) -> Result<(SharedSecret<Self>, Self::EncappedKey), HpkeError> {
                 // Put together the binding context used for all KDF operations
                 let suite_id = kem_suite_id::<Self>();
@@ -378,3 +379,73 @@ impl_dhkem!(
     0x0010,
     "Represents DHKEM(P-256, HKDF-SHA256)"
 );
+
+// Implement DHKEM(P-384, HKDF-SHA384)
+#[cfg(feature = "p384")]
+impl_dhkem!(
+    DhP384HkdfSha384,
+    crate::dhkex::ecdh_nistp::DhP384,
+    crate::kdf::HkdfSha384,
+    0x0011,
+    "Represents DHKEM(P-384, HKDF-SHA384)"
+);
+
+// Implement DHKEM(P-521, HKDF-SHA512)
+#[cfg(feature = "p521")]
+impl_dhkem!(
+    DhP521HkdfSha512,
+    crate::dhkex::ecdh_nistp::DhP521,
+    crate::kdf::HkdfSha512,
+    0x0012,
+    "Represents DHKEM(P-521, HKDF-SHA512)"
+);
+
+// Implement DHKEM(secp256k1, HKDF-SHA256). This is not part of the RFC 9180 registry, but is
+// useful for Bitcoin/Nostr-adjacent applications that already standardize on secp256k1
+#[cfg(feature = "secp256k1")]
+impl_dhkem!(
+    DhSecp256k1HkdfSha256,
+    crate::dhkex::secp256k1::Secp256k1,
+    crate::kdf::HkdfSha256,
+    0x0013,
+    "Represents DHKEM(secp256k1, HKDF-SHA256)"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TestRng;
+
+    /// Runs `Encap`/`Decap` for `$kem` and checks that both ends agree on the shared secret
+    macro_rules! round_trip_test {
+        ($test_name:ident, $kem:ty, $seed:literal) => {
+            #[test]
+            fn $test_name() {
+                let mut csprng = TestRng::new($seed);
+                let (sk_recip, pk_recip) = <$kem as KemTrait>::gen_keypair(&mut csprng);
+                let (sk_eph, _) = <$kem as KemTrait>::gen_keypair(&mut csprng);
+
+                let (ss_sender, encapped_key) =
+                    <$kem as KemTrait>::encap_with_eph(&pk_recip, None, sk_eph, &mut csprng)
+                        .expect("encap should succeed");
+                let ss_recip = <$kem as KemTrait>::decap(&sk_recip, None, &encapped_key)
+                    .expect("decap should succeed");
+
+                assert!(ss_sender.ct_eq(&ss_recip));
+            }
+        };
+    }
+
+    #[cfg(feature = "p384")]
+    round_trip_test!(dhp384_hkdfsha384_round_trips, DhP384HkdfSha384, 0x4001);
+
+    #[cfg(feature = "p521")]
+    round_trip_test!(dhp521_hkdfsha512_round_trips, DhP521HkdfSha512, 0x5201);
+
+    #[cfg(feature = "secp256k1")]
+    round_trip_test!(
+        dhsecp256k1_hkdfsha256_round_trips,
+        DhSecp256k1HkdfSha256,
+        0x256b
+    );
+}
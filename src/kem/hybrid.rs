@@ -0,0 +1,324 @@
+use crate::{
+    kdf::{labeled_expand, labeled_extract, HkdfSha256},
+    kem::{Kem as KemTrait, SharedSecret},
+    util::kem_suite_id,
+    Deserializable, HpkeError, Serializable,
+};
+
+use generic_array::{typenum, GenericArray};
+use ml_kem::{
+    kem::{Decapsulate, Encapsulate},
+    Ciphertext, Encoded, EncodedSizeUser, KemCore, MlKem768, B32,
+};
+use rand_core::{CryptoRng, RngCore};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret as X25519PrivateKey};
+
+/// The length in bytes of an ML-KEM-768 ciphertext
+const NCT_PQ: usize = 1088;
+/// The length in bytes of an ML-KEM-768 encapsulation (public) key
+const NPK_PQ: usize = 1184;
+/// The length in bytes of an ML-KEM-768 decapsulation (secret) key
+const NSK_PQ: usize = 2400;
+/// The length in bytes of an X25519 public or private key
+const N_X25519: usize = 32;
+
+/// The public key for [`X25519Kyber768Draft00`]. This is the concatenation of the ML-KEM-768
+/// encapsulation key and the X25519 public key
+#[derive(Clone)]
+pub struct PublicKey {
+    pq: ml_kem::kem::EncapsulationKey<<MlKem768 as KemCore>::Params>,
+    x25519: X25519PublicKey,
+}
+
+/// The private key for [`X25519Kyber768Draft00`]. This is the concatenation of the ML-KEM-768
+/// decapsulation key and the X25519 private key
+#[derive(Clone)]
+pub struct PrivateKey {
+    pq: ml_kem::kem::DecapsulationKey<<MlKem768 as KemCore>::Params>,
+    x25519: X25519PrivateKey,
+}
+
+impl PrivateKey {
+    /// Overwrites the X25519 component of this key with zeroes. This is a best-effort wipe with
+    /// no guarantee against compiler reordering or elision; enable the `zeroize` feature for a
+    /// guaranteed wipe on drop. The ML-KEM component manages its own erasure
+    pub fn non_secure_erase(&mut self) {
+        self.x25519 = X25519PrivateKey::from([0u8; 32]);
+    }
+}
+
+// `x25519_dalek::StaticSecret` already zeroizes its bytes on drop when this crate's `zeroize`
+// feature enables the matching feature on `x25519-dalek`, so no `Drop` impl is needed here
+
+impl Serializable for PublicKey {
+    type OutputSize = typenum::Sum<typenum::U1184, typenum::U32>;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        let mut buf = GenericArray::default();
+        buf[..NPK_PQ].copy_from_slice(&self.pq.as_bytes());
+        buf[NPK_PQ..].copy_from_slice(self.x25519.as_bytes());
+        buf
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        if encoded.len() != NPK_PQ + N_X25519 {
+            return Err(HpkeError::InvalidEncoding);
+        }
+        // `ml-kem`'s `EncodedSizeUser::from_bytes` takes a `hybrid_array::Array`, not a
+        // `generic_array::GenericArray`, so we convert through `TryFrom<&[u8]>` rather than
+        // `GenericArray::from_slice`
+        let pq_bytes: &Encoded<ml_kem::kem::EncapsulationKey<<MlKem768 as KemCore>::Params>> =
+            (&encoded[..NPK_PQ])
+                .try_into()
+                .map_err(|_| HpkeError::InvalidEncoding)?;
+        let pq = ml_kem::kem::EncapsulationKey::<<MlKem768 as KemCore>::Params>::from_bytes(
+            pq_bytes,
+        );
+        let mut x25519_bytes = [0u8; N_X25519];
+        x25519_bytes.copy_from_slice(&encoded[NPK_PQ..]);
+        Ok(PublicKey {
+            pq,
+            x25519: X25519PublicKey::from(x25519_bytes),
+        })
+    }
+}
+
+impl Serializable for PrivateKey {
+    type OutputSize = typenum::Sum<typenum::U2400, typenum::U32>;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        let mut buf = GenericArray::default();
+        buf[..NSK_PQ].copy_from_slice(&self.pq.as_bytes());
+        buf[NSK_PQ..].copy_from_slice(&self.x25519.to_bytes());
+        buf
+    }
+}
+
+impl Deserializable for PrivateKey {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        if encoded.len() != NSK_PQ + N_X25519 {
+            return Err(HpkeError::InvalidEncoding);
+        }
+        // See the matching comment in `PublicKey::from_bytes` re: `hybrid_array` vs
+        // `generic_array`
+        let pq_bytes: &Encoded<ml_kem::kem::DecapsulationKey<<MlKem768 as KemCore>::Params>> =
+            (&encoded[..NSK_PQ])
+                .try_into()
+                .map_err(|_| HpkeError::InvalidEncoding)?;
+        let pq = ml_kem::kem::DecapsulationKey::<<MlKem768 as KemCore>::Params>::from_bytes(
+            pq_bytes,
+        );
+        let mut x25519_bytes = [0u8; N_X25519];
+        x25519_bytes.copy_from_slice(&encoded[NSK_PQ..]);
+        Ok(PrivateKey {
+            pq,
+            x25519: X25519PrivateKey::from(x25519_bytes),
+        })
+    }
+}
+
+/// Holds the content of an encapsulated secret for [`X25519Kyber768Draft00`]. This is the
+/// concatenation of the ML-KEM-768 ciphertext and the ephemeral X25519 public key, in that order,
+/// per draft-westerbaan-cfrg-hpke-xyber768d00
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct X25519Kyber768Draft00EncappedKey {
+    ct_pq: Ciphertext<MlKem768>,
+    enc_x25519: X25519PublicKey,
+}
+
+impl Serializable for X25519Kyber768Draft00EncappedKey {
+    type OutputSize = typenum::Sum<typenum::U1088, typenum::U32>;
+
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize> {
+        let mut buf = GenericArray::default();
+        buf[..NCT_PQ].copy_from_slice(self.ct_pq.as_slice());
+        buf[NCT_PQ..].copy_from_slice(self.enc_x25519.as_bytes());
+        buf
+    }
+}
+
+impl Deserializable for X25519Kyber768Draft00EncappedKey {
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError> {
+        if encoded.len() != NCT_PQ + N_X25519 {
+            return Err(HpkeError::InvalidEncoding);
+        }
+        // `ml-kem`'s `Decapsulate::decapsulate` takes a `hybrid_array::Array`, not a
+        // `generic_array::GenericArray`, so we convert through `TryFrom<&[u8]>` rather than
+        // `GenericArray::from_slice`, same as the pubkey/privkey conversions above
+        let ct_pq: Ciphertext<MlKem768> = (&encoded[..NCT_PQ])
+            .try_into()
+            .map_err(|_| HpkeError::InvalidEncoding)?;
+        let mut x25519_bytes = [0u8; N_X25519];
+        x25519_bytes.copy_from_slice(&encoded[NCT_PQ..]);
+        Ok(X25519Kyber768Draft00EncappedKey {
+            ct_pq,
+            enc_x25519: X25519PublicKey::from(x25519_bytes),
+        })
+    }
+}
+
+/// Represents the hybrid post-quantum KEM X25519Kyber768Draft00, i.e. the combination of an
+/// X25519 Diffie-Hellman exchange (whose raw DH output is used directly, without HKDF) and
+/// ML-KEM-768 (Kyber768), as specified in
+/// draft-westerbaan-cfrg-hpke-xyber768d00. This KEM does not support the authenticated
+/// (`AuthEncap`/`AuthDecap`) modes; `sender_id_keypair`/`pk_sender_id` must be `None`
+pub struct X25519Kyber768Draft00;
+
+impl KemTrait for X25519Kyber768Draft00 {
+    /// draft-westerbaan-cfrg-hpke-xyber768d00 §3: Nsecret = 32 (X25519) + 32 (ML-KEM-768) = 64
+    #[doc(hidden)]
+    type NSecret = typenum::U64;
+
+    type PublicKey = PublicKey;
+    type PrivateKey = PrivateKey;
+    type EncappedKey = X25519Kyber768Draft00EncappedKey;
+
+    const KEM_ID: u16 = 0x0030;
+
+    /// Derives `d || z || x25519_sk` (96 bytes) from `ikm` via `LabeledExpand`, then feeds `d`
+    /// and `z` to ML-KEM-768's deterministic keygen (FIPS 203 Algorithm 16) and `x25519_sk`
+    /// directly into X25519's key clamping
+    fn derive_keypair(ikm: &[u8]) -> (Self::PrivateKey, Self::PublicKey) {
+        let suite_id = kem_suite_id::<Self>();
+        let (_, hkdf) = labeled_extract::<HkdfSha256>(None, &suite_id, b"dkp_prk", ikm);
+
+        let mut seed = [0u8; 96];
+        labeled_expand::<HkdfSha256>(&hkdf, &suite_id, b"sk", &[], &mut seed)
+            .expect("96 is a valid HKDF-Expand output length");
+
+        let d: B32 = seed[..32].try_into().expect("B32 is 32 bytes");
+        let z: B32 = seed[32..64].try_into().expect("B32 is 32 bytes");
+        let (pq_sk, pq_pk) = MlKem768::generate_deterministic(&d, &z);
+
+        let mut x25519_bytes = [0u8; N_X25519];
+        x25519_bytes.copy_from_slice(&seed[64..]);
+        let x25519_sk = X25519PrivateKey::from(x25519_bytes);
+        let x25519_pk = X25519PublicKey::from(&x25519_sk);
+
+        (
+            PrivateKey {
+                pq: pq_sk,
+                x25519: x25519_sk,
+            },
+            PublicKey {
+                pq: pq_pk,
+                x25519: x25519_pk,
+            },
+        )
+    }
+
+    fn gen_keypair<R: CryptoRng + RngCore>(
+        csprng: &mut R,
+    ) -> (Self::PrivateKey, Self::PublicKey) {
+        let (pq_sk, pq_pk) = MlKem768::generate(csprng);
+        let x25519_sk = X25519PrivateKey::random_from_rng(csprng);
+        let x25519_pk = X25519PublicKey::from(&x25519_sk);
+
+        (
+            PrivateKey {
+                pq: pq_sk,
+                x25519: x25519_sk,
+            },
+            PublicKey {
+                pq: pq_pk,
+                x25519: x25519_pk,
+            },
+        )
+    }
+
+    /// Runs the classical X25519 ephemeral DH exactly as `DhKeyExchange` does to get
+    /// `ss_x25519`/`enc_x25519`, and independently runs ML-KEM-768 `Encaps(pkR_pq)` to get
+    /// `(ct_pq, ss_pq)`. The combined shared secret is `ss_pq || ss_x25519`
+    #[doc(hidden)]
+    fn encap_with_eph<R: CryptoRng + RngCore>(
+        pk_recip: &Self::PublicKey,
+        sender_id_keypair: Option<&(Self::PrivateKey, Self::PublicKey)>,
+        sk_eph: Self::PrivateKey,
+        csprng: &mut R,
+    ) -> Result<(SharedSecret<Self>, Self::EncappedKey), HpkeError> {
+        if sender_id_keypair.is_some() {
+            return Err(HpkeError::EncapError);
+        }
+
+        let ss_x25519 = sk_eph.x25519.diffie_hellman(&pk_recip.x25519);
+        let enc_x25519 = X25519PublicKey::from(&sk_eph.x25519);
+
+        let (ct_pq, ss_pq) = pk_recip
+            .pq
+            .encapsulate(csprng)
+            .map_err(|_| HpkeError::EncapError)?;
+
+        let mut shared_secret = <SharedSecret<Self> as Default>::default();
+        shared_secret.0[..32].copy_from_slice(&ss_pq);
+        shared_secret.0[32..].copy_from_slice(ss_x25519.as_bytes());
+
+        Ok((
+            shared_secret,
+            X25519Kyber768Draft00EncappedKey {
+                ct_pq,
+                enc_x25519,
+            },
+        ))
+    }
+
+    /// Splits `enc` into the ML-KEM ciphertext and the X25519 ephemeral pubkey, decapsulates the
+    /// former with the PQ secret key and runs X25519 DH on the latter, then concatenates in the
+    /// same order as [`Self::encap_with_eph`]
+    #[doc(hidden)]
+    fn decap(
+        sk_recip: &Self::PrivateKey,
+        pk_sender_id: Option<&Self::PublicKey>,
+        encapped_key: &Self::EncappedKey,
+    ) -> Result<SharedSecret<Self>, HpkeError> {
+        if pk_sender_id.is_some() {
+            return Err(HpkeError::DecapError);
+        }
+
+        let ss_pq = sk_recip
+            .pq
+            .decapsulate(&encapped_key.ct_pq)
+            .map_err(|_| HpkeError::DecapError)?;
+        let ss_x25519 = sk_recip.x25519.diffie_hellman(&encapped_key.enc_x25519);
+
+        let mut shared_secret = <SharedSecret<Self> as Default>::default();
+        shared_secret.0[..32].copy_from_slice(&ss_pq);
+        shared_secret.0[32..].copy_from_slice(ss_x25519.as_bytes());
+
+        Ok(shared_secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TestRng;
+
+    #[test]
+    fn x25519kyber768draft00_round_trips() {
+        let mut csprng = TestRng::new(0x7687_6800);
+        let (sk_recip, pk_recip) = X25519Kyber768Draft00::gen_keypair(&mut csprng);
+        let (sk_eph, _) = X25519Kyber768Draft00::gen_keypair(&mut csprng);
+
+        let (ss_sender, encapped_key) =
+            X25519Kyber768Draft00::encap_with_eph(&pk_recip, None, sk_eph, &mut csprng)
+                .expect("encap should succeed");
+        let ss_recip = X25519Kyber768Draft00::decap(&sk_recip, None, &encapped_key)
+            .expect("decap should succeed");
+
+        assert!(ss_sender.ct_eq(&ss_recip));
+    }
+
+    #[test]
+    fn public_key_round_trips_through_bytes() {
+        let mut csprng = TestRng::new(0xF00D_F00D);
+        let (_, pk) = X25519Kyber768Draft00::gen_keypair(&mut csprng);
+
+        let encoded = pk.to_bytes();
+        let decoded = PublicKey::from_bytes(&encoded).expect("should deserialize");
+
+        assert_eq!(encoded, decoded.to_bytes());
+    }
+}
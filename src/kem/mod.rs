@@ -0,0 +1,107 @@
+use crate::{Deserializable, HpkeError, Serializable};
+
+use generic_array::{ArrayLength, GenericArray};
+use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+mod dhkem;
+#[cfg(feature = "kyber")]
+mod hybrid;
+#[cfg(feature = "kem-traits")]
+pub mod rustcrypto_adapter;
+
+pub use dhkem::*;
+#[cfg(feature = "kyber")]
+pub use hybrid::X25519Kyber768Draft00;
+
+/// Represents key encapsulation functionality for an HPKE ciphersuite
+pub trait Kem {
+    /// The size, in bytes, of the shared secret this KEM produces
+    #[doc(hidden)]
+    type NSecret: ArrayLength<u8>;
+
+    /// This KEM's public key type
+    type PublicKey: Clone + Serializable + Deserializable;
+    /// This KEM's private key type
+    type PrivateKey: Clone + Serializable + Deserializable;
+    /// This KEM's encapsulated key type
+    type EncappedKey: Clone + Serializable + Deserializable;
+
+    /// The algorithm identifier for this KEM, as given in RFC 9180 §7.1
+    const KEM_ID: u16;
+
+    /// Deterministically derives a keypair from the given input keying material
+    fn derive_keypair(ikm: &[u8]) -> (Self::PrivateKey, Self::PublicKey);
+
+    /// Generates a random keypair using the given RNG
+    fn gen_keypair<R: CryptoRng + RngCore>(csprng: &mut R) -> (Self::PrivateKey, Self::PublicKey);
+
+    /// Derives a shared secret given the recipient's pubkey and an ephemeral private key,
+    /// optionally tying the sender's identity to the shared secret. `csprng` is passed through
+    /// for KEMs (e.g. the hybrid PQ KEM) that need fresh randomness beyond `sk_eph` itself; the
+    /// DH-KEMs ignore it, since their only randomness is `sk_eph`, already generated by the caller
+    #[doc(hidden)]
+    fn encap_with_eph<R: CryptoRng + RngCore>(
+        pk_recip: &Self::PublicKey,
+        sender_id_keypair: Option<&(Self::PrivateKey, Self::PublicKey)>,
+        sk_eph: Self::PrivateKey,
+        csprng: &mut R,
+    ) -> Result<(SharedSecret<Self>, Self::EncappedKey), HpkeError>
+    where
+        Self: Sized;
+
+    /// Derives a shared secret given the encapsulated key and the recipient's private key,
+    /// optionally checking the sender's identity
+    #[doc(hidden)]
+    fn decap(
+        sk_recip: &Self::PrivateKey,
+        pk_sender_id: Option<&Self::PublicKey>,
+        encapped_key: &Self::EncappedKey,
+    ) -> Result<SharedSecret<Self>, HpkeError>
+    where
+        Self: Sized;
+}
+
+/// A shared secret derived from a KEM's key exchange operation. This is the quantity fed into
+/// `KeySchedule` to derive the AEAD key/nonce in use
+pub struct SharedSecret<K: Kem + ?Sized>(pub(crate) GenericArray<u8, K::NSecret>);
+
+impl<K: Kem + ?Sized> Default for SharedSecret<K> {
+    fn default() -> Self {
+        SharedSecret(GenericArray::default())
+    }
+}
+
+impl<K: Kem + ?Sized> Clone for SharedSecret<K> {
+    fn clone(&self) -> Self {
+        SharedSecret(self.0.clone())
+    }
+}
+
+impl<K: Kem + ?Sized> SharedSecret<K> {
+    /// Compares two shared secrets in constant time, so that callers checking a derived secret
+    /// against an expected value don't leak timing information about where the first mismatching
+    /// byte is. We deliberately don't derive `PartialEq`/`Eq` (or `PartialOrd`/`Ord`/`Hash`) on
+    /// this type, to discourage accidental variable-time comparison of secret material
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+
+    /// Overwrites this shared secret's bytes with zeroes. This is a best-effort wipe: unlike the
+    /// `zeroize`-gated `Drop` impl below, it gives no guarantee against compiler reordering or
+    /// elision, but it's useful for callers who want to scrub a secret before it goes out of
+    /// scope without depending on the `zeroize` feature
+    pub fn non_secure_erase(&mut self) {
+        for byte in self.0.iter_mut() {
+            *byte = 0;
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K: Kem + ?Sized> Drop for SharedSecret<K> {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
@@ -0,0 +1,88 @@
+//! Adapters implementing the [RustCrypto `kem` crate](https://docs.rs/kem)'s
+//! [`Encapsulate`]/[`Decapsulate`] traits on top of this crate's [`Kem`] trait, so that any KEM
+//! defined here (the DH-KEMs, the hybrid KEM, etc.) can be used behind the generic `kem` crate
+//! abstraction alongside other KEMs like Saber or X3DH
+//!
+//! The `kem` crate was redesigned in its `0.3.0` release around a heavier `Kem`/`KeyExport`/
+//! `TryKeyInit` type family geared towards fixed-size, typenum-keyed KEMs, which doesn't fit this
+//! crate's generic [`Kem`](crate::kem::Kem) trait. We target `kem = "=0.3.0-pre.0"`
+//! instead, which still has the simple, parameterized `Encapsulate<EK, SS>`/`Decapsulate<EK, SS>`
+//! shape this module was written against
+
+use crate::kem::{Kem as KemTrait, SharedSecret};
+
+use kem::{Decapsulate, Encapsulate};
+use rand_core::CryptoRngCore;
+
+/// An error from an [`Encapsulate`]/[`Decapsulate`] call, wrapping this crate's [`HpkeError`]
+///
+/// [`HpkeError`]: crate::HpkeError
+#[derive(Debug, Eq, PartialEq)]
+pub struct RustCryptoKemError(pub crate::HpkeError);
+
+impl core::fmt::Display for RustCryptoKemError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RustCryptoKemError {}
+
+/// Wraps a KEM's public key so we can impl the orphan `kem::Encapsulate` trait on it without
+/// conflicting with any downstream impls on `K::PublicKey` itself
+pub struct EncapsulatingKey<'a, K: KemTrait>(pub &'a K::PublicKey);
+
+impl<'a, K: KemTrait> Encapsulate<K::EncappedKey, SharedSecret<K>> for EncapsulatingKey<'a, K> {
+    type Error = RustCryptoKemError;
+
+    fn encapsulate(
+        &self,
+        csprng: &mut impl CryptoRngCore,
+    ) -> Result<(K::EncappedKey, SharedSecret<K>), Self::Error> {
+        let sk_eph = {
+            let (sk, _) = K::gen_keypair(csprng);
+            sk
+        };
+        K::encap_with_eph(self.0, None, sk_eph, csprng)
+            .map(|(ss, enc)| (enc, ss))
+            .map_err(RustCryptoKemError)
+    }
+}
+
+/// Wraps a KEM's private key so we can impl the orphan `kem::Decapsulate` trait on it without
+/// conflicting with any downstream impls on `K::PrivateKey` itself
+pub struct DecapsulatingKey<'a, K: KemTrait>(pub &'a K::PrivateKey);
+
+impl<'a, K: KemTrait> Decapsulate<K::EncappedKey, SharedSecret<K>> for DecapsulatingKey<'a, K> {
+    type Error = RustCryptoKemError;
+
+    fn decapsulate(
+        &self,
+        encapped_key: &K::EncappedKey,
+    ) -> Result<SharedSecret<K>, Self::Error> {
+        K::decap(self.0, None, encapped_key).map_err(RustCryptoKemError)
+    }
+}
+
+#[cfg(all(test, feature = "x25519-dalek"))]
+mod tests {
+    use super::*;
+    use crate::{kem::X25519HkdfSha256, util::TestRng};
+
+    #[test]
+    fn adapter_round_trips() {
+        let mut csprng = TestRng::new(0xADA9_7E00);
+        let (sk_recip, pk_recip) = X25519HkdfSha256::gen_keypair(&mut csprng);
+
+        let (encapped_key, ss_sender) =
+            EncapsulatingKey::<X25519HkdfSha256>(&pk_recip)
+                .encapsulate(&mut csprng)
+                .expect("encapsulate should succeed");
+        let ss_recip = DecapsulatingKey::<X25519HkdfSha256>(&sk_recip)
+            .decapsulate(&encapped_key)
+            .expect("decapsulate should succeed");
+
+        assert!(ss_sender.ct_eq(&ss_recip));
+    }
+}
@@ -0,0 +1,39 @@
+//! `hpke` is a Rust implementation of Hybrid Public Key Encryption (HPKE) as specified in RFC
+//! 9180
+
+#[macro_use]
+mod util;
+
+pub mod config;
+pub mod dhkex;
+pub mod kdf;
+pub mod kem;
+
+use generic_array::GenericArray;
+
+/// An error from encryption, decryption, encapsulation, or decapsulation
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HpkeError {
+    /// An error while encapsulating a shared secret
+    EncapError,
+    /// An error while decapsulating a shared secret
+    DecapError,
+    /// The given bytes could not be deserialized into the expected type
+    InvalidEncoding,
+}
+
+/// Types that can be serialized to a fixed-size byte array
+pub trait Serializable {
+    /// The length, in bytes, of this type's serialization
+    type OutputSize: generic_array::ArrayLength<u8>;
+
+    /// Serializes this object into a byte array
+    fn to_bytes(&self) -> GenericArray<u8, Self::OutputSize>;
+}
+
+/// Types that can be deserialized from a byte slice
+pub trait Deserializable: Sized {
+    /// Deserializes this object from a byte slice
+    fn from_bytes(encoded: &[u8]) -> Result<Self, HpkeError>;
+}
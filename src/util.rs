@@ -0,0 +1,66 @@
+use crate::kem::Kem as KemTrait;
+
+/// Computes the `suite_id` used in the `LabeledExtract`/`LabeledExpand` calls inside a KEM's
+/// `derive_keypair`/`encap_with_eph`/`decap`. This is `"KEM" || I2OSP(kem_id, 2)`
+pub(crate) fn kem_suite_id<K: KemTrait>() -> [u8; 5] {
+    let mut suite_id = [0u8; 5];
+    suite_id[..3].copy_from_slice(b"KEM");
+    suite_id[3..].copy_from_slice(&K::KEM_ID.to_be_bytes());
+    suite_id
+}
+
+/// Concatenates the given byte slices into a fixed-size, stack-allocated buffer of size
+/// `$maxlen`, avoiding a heap allocation. Returns `(buffer, used_length)`
+#[macro_export]
+macro_rules! concat_with_known_maxlen {
+    ($maxlen:expr, $($slice:expr),+ $(,)?) => {{
+        let mut buf = [0u8; $maxlen];
+        let mut len = 0;
+        $(
+            let slice: &[u8] = $slice;
+            buf[len..len + slice.len()].copy_from_slice(slice);
+            len += slice.len();
+        )+
+        (buf, len)
+    }};
+}
+
+/// A fixed-seed, non-cryptographic RNG used to make KEM encap/decap round-trip tests
+/// reproducible. Never use this outside of tests
+#[cfg(test)]
+pub(crate) struct TestRng(u64);
+
+#[cfg(test)]
+impl TestRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        TestRng(seed)
+    }
+}
+
+#[cfg(test)]
+impl rand_core::RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // One splitmix64 step. Deterministic, not suitable for anything but tests
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl rand_core::CryptoRng for TestRng {}